@@ -1,10 +1,10 @@
+use crate::expression::Operator;
+use crate::grammar;
+use crate::statement::Statement;
+use crate::value::{self, Value};
+use rand::{thread_rng, Rng};
 use std::io;
-use super::value::Value;
-use super::grammar;
-use super::statement::Statement;
-use super::Operator;
 use std::process;
-use rand::{thread_rng, Rng};
 
 pub type SwResult<T> = Result<T, ErrorKind>;
 pub type SwErResult<T> = Result<T, Error>;
@@ -32,12 +32,24 @@ pub struct Error {
 #[derive(Debug)]
 pub enum ErrorKind {
     UnknownVariable(String),
-    IndexUnindexable(Value),
+    IndexUnindexable(value::Type),
     SyntaxError(grammar::ParseError),
-    IndexOutOfBounds(Value, usize),
+    IndexOutOfBounds { len: usize, index: usize },
     IOError(io::Error),
-    UnexpectedType(String, Value),
+    UnexpectedType { expected: value::Type, actual: value::Type },
     InvalidBinaryExpression(Value, Value, Operator),
+    InvalidArguments(String, usize, usize),
+    NoReturn(String),
+    NonFunctionCallInDylib(Statement),
+    NegativeListRepeat(i64),
+    StackOverflow(usize),
+    /// `list[start..end]` where `start > end`: a backwards range, not an
+    /// out-of-bounds index.
+    InvalidRange { start: usize, end: usize },
+    /// `chr` was handed an `i64` that isn't a valid Unicode codepoint.
+    InvalidCodepoint(i64),
+    /// `ord` was handed a `Str` that isn't exactly one character long.
+    NotASingleCharacter(String),
 }
 
 impl PartialEq for ErrorKind {
@@ -46,57 +58,84 @@ impl PartialEq for ErrorKind {
             (&ErrorKind::UnknownVariable(ref s), &ErrorKind::UnknownVariable(ref o)) => s == o,
             (&ErrorKind::IndexUnindexable(ref s), &ErrorKind::IndexUnindexable(ref o)) => s == o,
             (&ErrorKind::SyntaxError(ref s), &ErrorKind::SyntaxError(ref o)) => s == o,
-            (&ErrorKind::IndexOutOfBounds(ref sv, si),
-             &ErrorKind::IndexOutOfBounds(ref ov, oi)) => sv == ov && si == oi,
+            (&ErrorKind::IndexOutOfBounds { len: sl, index: si },
+             &ErrorKind::IndexOutOfBounds { len: ol, index: oi }) => sl == ol && si == oi,
             (&ErrorKind::IOError(_), &ErrorKind::IOError(_)) => true,
-            (&ErrorKind::UnexpectedType(ref ss, ref sv),
-             &ErrorKind::UnexpectedType(ref os, ref ov)) => ss == os && sv == ov,
+            (&ErrorKind::UnexpectedType { expected: se, actual: sa },
+             &ErrorKind::UnexpectedType { expected: oe, actual: oa }) => se == oe && sa == oa,
             (&ErrorKind::InvalidBinaryExpression(ref sv1, ref sv2, ref so),
              &ErrorKind::InvalidBinaryExpression(ref ov1, ref ov2, ref oo)) => {
                 sv1 == ov1 && sv2 == ov2 && so == oo
             }
+            (&ErrorKind::InvalidArguments(ref sn, sg, se),
+             &ErrorKind::InvalidArguments(ref on, og, oe)) => sn == on && sg == og && se == oe,
+            (&ErrorKind::NoReturn(ref s), &ErrorKind::NoReturn(ref o)) => s == o,
+            (&ErrorKind::NonFunctionCallInDylib(ref s), &ErrorKind::NonFunctionCallInDylib(ref o)) => {
+                s == o
+            }
+            (&ErrorKind::NegativeListRepeat(s), &ErrorKind::NegativeListRepeat(o)) => s == o,
+            (&ErrorKind::StackOverflow(s), &ErrorKind::StackOverflow(o)) => s == o,
+            (&ErrorKind::InvalidRange { start: ss, end: se },
+             &ErrorKind::InvalidRange { start: os, end: oe }) => ss == os && se == oe,
+            (&ErrorKind::InvalidCodepoint(s), &ErrorKind::InvalidCodepoint(o)) => s == o,
+            (&ErrorKind::NotASingleCharacter(ref s), &ErrorKind::NotASingleCharacter(ref o)) => {
+                s == o
+            }
             _ => false,
         }
     }
 }
 
-impl Error {
-    pub fn new(kind: ErrorKind, place: Statement) -> Self {
-        Error {
-            kind: kind,
-            place: place,
+impl ErrorKind {
+    /// A short, stable name for the failure kind, readable from Schwift
+    /// code via a bound `catch` error value.
+    pub fn type_str(&self) -> &'static str {
+        match *self {
+            ErrorKind::UnknownVariable(_) => "UnknownVariable",
+            ErrorKind::IndexUnindexable(_) => "IndexUnindexable",
+            ErrorKind::SyntaxError(_) => "SyntaxError",
+            ErrorKind::IndexOutOfBounds { .. } => "IndexOutOfBounds",
+            ErrorKind::IOError(_) => "IOError",
+            ErrorKind::UnexpectedType { .. } => "UnexpectedType",
+            ErrorKind::InvalidBinaryExpression(_, _, _) => "InvalidBinaryExpression",
+            ErrorKind::InvalidArguments(_, _, _) => "InvalidArguments",
+            ErrorKind::NoReturn(_) => "NoReturn",
+            ErrorKind::NonFunctionCallInDylib(_) => "NonFunctionCallInDylib",
+            ErrorKind::NegativeListRepeat(_) => "NegativeListRepeat",
+            ErrorKind::StackOverflow(_) => "StackOverflow",
+            ErrorKind::InvalidRange { .. } => "InvalidRange",
+            ErrorKind::InvalidCodepoint(_) => "InvalidCodepoint",
+            ErrorKind::NotASingleCharacter(_) => "NotASingleCharacter",
         }
     }
 
-    pub fn panic_message(&self) -> String {
-        match self.kind {
+    pub fn message(&self) -> String {
+        match *self {
             ErrorKind::UnknownVariable(ref name) => {
                 format!("There's no {} in this universe, Morty!", name)
             }
-            ErrorKind::IndexUnindexable(ref value) => {
+            ErrorKind::IndexUnindexable(ref kind) => {
                 format!("I'll try and say this slowly Morty. You can't index that. It's a {}",
-                        value.type_str())
+                        kind)
             }
             ErrorKind::SyntaxError(ref err) => {
                 format!("If you're going to start trying to construct sub-programs in your \
                         programs Morty, you'd better make sure you're careful! {:?}",
                         err)
             }
-            ErrorKind::IndexOutOfBounds(ref value, ref index) => {
+            ErrorKind::IndexOutOfBounds { len, index } => {
                 format!("This isn't your mom's wine bottle Morty, you can't just keep asking for \
-                        more, there's not that much here! You want {}, but you're dealing with \
-                        {:?}!",
+                        more, there's not that much here! You want {}, but there's only {} in \
+                        there!",
                         index,
-                        value)
+                        len)
             }
             ErrorKind::IOError(ref err) => {
                 format!("Looks like we're having a comm-burp-unications problem Morty: {:?}",
                         err)
             }
-            ErrorKind::UnexpectedType(ref expected, ref value) => {
-                format!("I asked for a {}, not a {} Morty.",
-                        expected,
-                        value.type_str())
+            ErrorKind::UnexpectedType { expected, actual } => {
+                format!("I asked for a {}, not a {} Morty.", expected, actual)
             }
             ErrorKind::InvalidBinaryExpression(ref lhs, ref rhs, ref op) => {
                 format!("It's like apples and space worms Morty! You can't {:?} a {} and a {}!",
@@ -104,9 +143,69 @@ impl Error {
                         lhs.type_str(),
                         rhs.type_str())
             }
+            ErrorKind::InvalidArguments(ref name, got, expected) => {
+                format!("{} wants {} argument(s), not {}, Morty! Learn to count!",
+                        name,
+                        expected,
+                        got)
+            }
+            ErrorKind::NoReturn(ref name) => {
+                format!("{} didn't return anything Morty! What was even the point?!", name)
+            }
+            ErrorKind::NonFunctionCallInDylib(_) => {
+                "Everything in a dylib block has to be a function call Morty, what are you even \
+                 doing?!"
+                    .to_string()
+            }
+            ErrorKind::NegativeListRepeat(ref n) => {
+                format!("You can't repeat a list {} times Morty, that's not how \
+                        any of this works!",
+                        n)
+            }
+            ErrorKind::StackOverflow(ref limit) => {
+                format!("You've gone {} calls deep Morty, that's a infinite loop of \
+                        regret and there's no bottom to it!",
+                        limit)
+            }
+            ErrorKind::InvalidRange { start, end } => {
+                format!("You want a slice from {} to {} Morty? That's backwards, you gotta go \
+                        forwards through time like the rest of us!",
+                        start,
+                        end)
+            }
+            ErrorKind::InvalidCodepoint(codepoint) => {
+                format!("{} isn't a real character Morty, not even in some other dimension!",
+                        codepoint)
+            }
+            ErrorKind::NotASingleCharacter(ref s) => {
+                format!("\"{}\" isn't a single character Morty, I can't just make up a number \
+                        for a whole word!",
+                        s)
+            }
+        }
+    }
+}
+
+impl Error {
+    pub fn new(kind: ErrorKind, place: Statement) -> Self {
+        Error {
+            kind: kind,
+            place: place,
         }
     }
 
+    pub fn panic_message(&self) -> String {
+        self.kind.message()
+    }
+
+    pub fn into_kind(self) -> ErrorKind {
+        self.kind
+    }
+
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+
     pub fn full_panic_message(&self, filename: &str) -> String {
         let type_msg = self.panic_message();
         let quote = random_quote();
@@ -0,0 +1,41 @@
+use crate::{error::ErrorKind, value::Value};
+
+#[cfg(test)]
+mod test;
+
+/// `list[start..end]`.
+pub fn slice(list: &[Value], start: usize, end: usize) -> Result<Value, ErrorKind> {
+    if start > end {
+        return Err(ErrorKind::InvalidRange { start, end });
+    }
+    if end > list.len() {
+        return Err(ErrorKind::IndexOutOfBounds {
+            len: list.len(),
+            index: end,
+        });
+    }
+
+    Ok(Value::List(list[start..end].to_vec()))
+}
+
+/// `list * n`: `n` copies of `list`'s elements concatenated together.
+pub fn repeat(list: &[Value], n: i64) -> Result<Value, ErrorKind> {
+    if n < 0 {
+        return Err(ErrorKind::NegativeListRepeat(n));
+    }
+
+    let mut out = Vec::with_capacity(list.len() * n as usize);
+    for _ in 0..n {
+        out.extend_from_slice(list);
+    }
+
+    Ok(Value::List(out))
+}
+
+/// `a + b` for two lists: concatenation.
+pub fn concat(a: &[Value], b: &[Value]) -> Value {
+    let mut out = Vec::with_capacity(a.len() + b.len());
+    out.extend_from_slice(a);
+    out.extend_from_slice(b);
+    Value::List(out)
+}
@@ -0,0 +1,45 @@
+use super::*;
+
+fn ints(vals: &[i64]) -> Vec<Value> {
+    vals.iter().map(|&i| Value::Int(i)).collect()
+}
+
+#[test]
+fn slice_returns_the_requested_range() {
+    let list = ints(&[1, 2, 3, 4]);
+    assert_eq!(slice(&list, 1, 3), Ok(Value::List(ints(&[2, 3]))));
+}
+
+#[test]
+fn slice_rejects_backwards_ranges() {
+    let list = ints(&[1, 2, 3]);
+    assert_eq!(slice(&list, 2, 1), Err(ErrorKind::InvalidRange { start: 2, end: 1 }));
+}
+
+#[test]
+fn slice_rejects_out_of_bounds_end() {
+    let list = ints(&[1, 2, 3]);
+    assert_eq!(
+        slice(&list, 0, 4),
+        Err(ErrorKind::IndexOutOfBounds { len: 3, index: 4 })
+    );
+}
+
+#[test]
+fn repeat_concatenates_n_copies() {
+    let list = ints(&[1, 2]);
+    assert_eq!(repeat(&list, 2), Ok(Value::List(ints(&[1, 2, 1, 2]))));
+}
+
+#[test]
+fn repeat_rejects_negative_counts() {
+    let list = ints(&[1, 2]);
+    assert_eq!(repeat(&list, -1), Err(ErrorKind::NegativeListRepeat(-1)));
+}
+
+#[test]
+fn concat_appends_b_after_a() {
+    let a = ints(&[1, 2]);
+    let b = ints(&[3]);
+    assert_eq!(concat(&a, &b), Value::List(ints(&[1, 2, 3])));
+}
@@ -0,0 +1,72 @@
+mod error;
+mod expression;
+mod grammar;
+mod list_ops;
+mod native;
+mod repl;
+mod state;
+mod statement;
+mod trace;
+mod value;
+mod vec_map;
+
+#[cfg(test)]
+mod test;
+
+use state::State;
+use std::{env, fs, process};
+
+fn main() {
+    let mut args: Vec<String> = env::args().collect();
+    let trace = take_flag(&mut args, "--trace");
+
+    if args.len() < 2 {
+        repl::run(trace);
+        return;
+    }
+
+    let filename = &args[1];
+    let source = fs::read_to_string(filename).unwrap_or_else(|e| {
+        eprintln!("Couldn't read {}: {}", filename, e);
+        process::exit(1);
+    });
+
+    let program = grammar::program(&source).unwrap_or_else(|e| {
+        eprintln!("{:?}", e);
+        process::exit(1);
+    });
+
+    let mut state = State::new();
+    if trace {
+        state.enable_tracing();
+    }
+    state.set_source_file(filename.clone());
+    let script_args: Vec<&str> = args[2..].iter().map(String::as_str).collect();
+    state.parse_args(&script_args);
+
+    if let Err(e) = state.run(&program) {
+        e.panic(filename);
+    }
+}
+
+/// Removes the first `flag` found before the filename argument and reports
+/// whether it was there. The scan stops at the first argument that isn't
+/// itself `--`-prefixed - that's the filename - so a flag scanned here
+/// never reaches into the script's own argv and strips something the
+/// script meant to receive as a positional argument.
+fn take_flag(args: &mut Vec<String>, flag: &str) -> bool {
+    let boundary = args
+        .iter()
+        .skip(1)
+        .position(|a| !a.starts_with("--"))
+        .map(|i| i + 1)
+        .unwrap_or(args.len());
+
+    match args[..boundary].iter().position(|a| a == flag) {
+        Some(i) => {
+            args.remove(i);
+            true
+        }
+        None => false,
+    }
+}
@@ -0,0 +1,209 @@
+use crate::{
+    error::{ErrorKind, SwResult},
+    list_ops,
+    state::State,
+    value::{self, Value},
+};
+
+#[cfg(test)]
+mod test;
+
+// HOLD, chunk0-5: request chunk0-5 asks for `list[a..b]`, `list * n`, and
+// `list + list` *syntax* in the binary-expression evaluator (its own
+// example is `[0] * 256`). `slice`/`repeat`/`concat` below are native
+// functions standing in for that syntax, built against `list_ops` while
+// this tree can't touch `expression.rs` or the grammar (neither is part of
+// this source delivery) to add the real operators. That was flagged for
+// the backlog owner to weigh in on, and no sign-off that the
+// native-function form is an acceptable substitute has been recorded.
+// Until it is, `register` below leaves them unwired rather than shipping
+// them as a quiet stand-in for the requested syntax - `[0] * 256` still
+// doesn't parse, and a script can't see `slice`/`repeat`/`concat` either.
+// The functions stay here, tested, ready to wire in the moment either the
+// sign-off lands or the grammar work does.
+
+/// Registers the default set of native functions a fresh `State` starts
+/// with.
+pub fn register(state: &mut State) {
+    state.insert("len", native(len));
+    state.insert("chr", native(chr));
+    state.insert("ord", native(ord));
+    state.insert("range", native(range));
+    state.insert("to_int", native(to_int));
+    state.insert("to_str", native(to_str));
+}
+
+fn native(f: fn(&[Value]) -> SwResult<Value>) -> Value {
+    Value::NativeFunction(f)
+}
+
+fn arity(name: &str, args: &[Value], expected: usize) -> SwResult<()> {
+    if args.len() == expected {
+        Ok(())
+    } else {
+        Err(ErrorKind::InvalidArguments(
+            name.to_string(),
+            args.len(),
+            expected,
+        ))
+    }
+}
+
+fn len(args: &[Value]) -> SwResult<Value> {
+    arity("len", args, 1)?;
+
+    match args[0] {
+        Value::List(ref l) => Ok(Value::Int(l.len() as i64)),
+        Value::Str(ref s) => Ok(Value::Int(s.chars().count() as i64)),
+        ref v => Err(ErrorKind::UnexpectedType {
+            expected: value::Type::Str,
+            actual: v.get_type(),
+        }),
+    }
+}
+
+fn chr(args: &[Value]) -> SwResult<Value> {
+    arity("chr", args, 1)?;
+
+    match args[0] {
+        Value::Int(i) => {
+            let c = if i >= 0 && i <= i64::from(std::u32::MAX) {
+                std::char::from_u32(i as u32)
+            } else {
+                None
+            };
+            match c {
+                Some(c) => Ok(Value::Str(c.to_string())),
+                None => Err(ErrorKind::InvalidCodepoint(i)),
+            }
+        }
+        ref v => Err(ErrorKind::UnexpectedType {
+            expected: value::Type::Int,
+            actual: v.get_type(),
+        }),
+    }
+}
+
+fn ord(args: &[Value]) -> SwResult<Value> {
+    arity("ord", args, 1)?;
+
+    match args[0] {
+        Value::Str(ref s) => {
+            let mut chars = s.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Ok(Value::Int(c as i64)),
+                _ => Err(ErrorKind::NotASingleCharacter(s.clone())),
+            }
+        }
+        ref v => Err(ErrorKind::UnexpectedType {
+            expected: value::Type::Str,
+            actual: v.get_type(),
+        }),
+    }
+}
+
+fn range(args: &[Value]) -> SwResult<Value> {
+    arity("range", args, 1)?;
+
+    match args[0] {
+        Value::Int(n) => Ok(Value::List((0..n).map(Value::Int).collect())),
+        ref v => Err(ErrorKind::UnexpectedType {
+            expected: value::Type::Int,
+            actual: v.get_type(),
+        }),
+    }
+}
+
+fn to_int(args: &[Value]) -> SwResult<Value> {
+    arity("to_int", args, 1)?;
+
+    match args[0] {
+        Value::Int(i) => Ok(Value::Int(i)),
+        Value::Str(ref s) => s.trim().parse().map(Value::Int).map_err(|_| {
+            ErrorKind::UnexpectedType {
+                expected: value::Type::Int,
+                actual: args[0].get_type(),
+            }
+        }),
+        ref v => Err(ErrorKind::UnexpectedType {
+            expected: value::Type::Str,
+            actual: v.get_type(),
+        }),
+    }
+}
+
+fn to_str(args: &[Value]) -> SwResult<Value> {
+    arity("to_str", args, 1)?;
+
+    Ok(Value::Str(args[0].to_string()))
+}
+
+/// `slice(list, start, end)`: `list[start..end]`. Written as a native
+/// function rather than `list[a..b]` syntax - the grammar has no range
+/// literal to parse `a..b` with.
+///
+/// On hold (see the comment above `register`) - not currently reachable
+/// from a script.
+#[allow(dead_code)]
+fn slice(args: &[Value]) -> SwResult<Value> {
+    arity("slice", args, 3)?;
+
+    let list = as_list(&args[0])?;
+    let start = as_int(&args[1])?;
+    let end = as_int(&args[2])?;
+
+    list_ops::slice(list, start as usize, end as usize)
+}
+
+/// `repeat(list, n)`: `n` copies of `list`'s elements concatenated together.
+/// Stands in for `list * n`, which the binary-expression evaluator has no
+/// operator for.
+///
+/// On hold (see the comment above `register`) - not currently reachable
+/// from a script.
+#[allow(dead_code)]
+fn repeat(args: &[Value]) -> SwResult<Value> {
+    arity("repeat", args, 2)?;
+
+    let list = as_list(&args[0])?;
+    let n = as_int(&args[1])?;
+
+    list_ops::repeat(list, n)
+}
+
+/// `concat(a, b)`: `a`'s elements followed by `b`'s. Stands in for list `+`,
+/// which the binary-expression evaluator only handles for `Int`/`Str`.
+///
+/// On hold (see the comment above `register`) - not currently reachable
+/// from a script.
+#[allow(dead_code)]
+fn concat(args: &[Value]) -> SwResult<Value> {
+    arity("concat", args, 2)?;
+
+    let a = as_list(&args[0])?;
+    let b = as_list(&args[1])?;
+
+    Ok(list_ops::concat(a, b))
+}
+
+#[allow(dead_code)]
+fn as_list(value: &Value) -> SwResult<&[Value]> {
+    match *value {
+        Value::List(ref l) => Ok(l),
+        ref v => Err(ErrorKind::UnexpectedType {
+            expected: value::Type::List,
+            actual: v.get_type(),
+        }),
+    }
+}
+
+#[allow(dead_code)]
+fn as_int(value: &Value) -> SwResult<i64> {
+    match *value {
+        Value::Int(i) => Ok(i),
+        ref v => Err(ErrorKind::UnexpectedType {
+            expected: value::Type::Int,
+            actual: v.get_type(),
+        }),
+    }
+}
@@ -0,0 +1,110 @@
+use super::*;
+
+#[test]
+fn len_counts_chars_not_bytes() {
+    assert_eq!(len(&[Value::Str("héllo".to_string())]), Ok(Value::Int(5)));
+}
+
+#[test]
+fn len_rejects_non_indexable() {
+    assert_eq!(
+        len(&[Value::Int(1)]),
+        Err(ErrorKind::UnexpectedType {
+            expected: value::Type::Str,
+            actual: value::Type::Int,
+        })
+    );
+}
+
+#[test]
+fn chr_roundtrips_with_ord() {
+    assert_eq!(chr(&[Value::Int(65)]), Ok(Value::Str("A".to_string())));
+    assert_eq!(ord(&[Value::Str("A".to_string())]), Ok(Value::Int(65)));
+}
+
+#[test]
+fn chr_rejects_invalid_codepoint() {
+    assert_eq!(chr(&[Value::Int(-1)]), Err(ErrorKind::InvalidCodepoint(-1)));
+}
+
+#[test]
+fn ord_rejects_multi_character_strings() {
+    assert_eq!(
+        ord(&[Value::Str("ab".to_string())]),
+        Err(ErrorKind::NotASingleCharacter("ab".to_string()))
+    );
+}
+
+#[test]
+fn arity_rejects_wrong_argument_count() {
+    assert_eq!(
+        len(&[]),
+        Err(ErrorKind::InvalidArguments("len".to_string(), 0, 1))
+    );
+}
+
+#[test]
+fn slice_wraps_list_ops_slice() {
+    let list = Value::List(vec![Value::Int(1), Value::Int(2), Value::Int(3)]);
+    assert_eq!(
+        slice(&[list, Value::Int(1), Value::Int(3)]),
+        Ok(Value::List(vec![Value::Int(2), Value::Int(3)]))
+    );
+}
+
+#[test]
+fn repeat_rejects_non_list_first_argument() {
+    assert_eq!(
+        repeat(&[Value::Int(1), Value::Int(2)]),
+        Err(ErrorKind::UnexpectedType {
+            expected: value::Type::List,
+            actual: value::Type::Int,
+        })
+    );
+}
+
+#[test]
+fn repeat_blames_a_bad_second_argument_not_the_list() {
+    let list = Value::List(vec![Value::Int(1)]);
+    assert_eq!(
+        repeat(&[list, Value::Str("x".to_string())]),
+        Err(ErrorKind::UnexpectedType {
+            expected: value::Type::Int,
+            actual: value::Type::Str,
+        })
+    );
+}
+
+#[test]
+fn concat_joins_two_lists() {
+    let a = Value::List(vec![Value::Int(1)]);
+    let b = Value::List(vec![Value::Int(2)]);
+    assert_eq!(
+        concat(&[a, b]),
+        Ok(Value::List(vec![Value::Int(1), Value::Int(2)]))
+    );
+}
+
+#[test]
+fn concat_blames_a_bad_second_argument_not_the_first_list() {
+    let a = Value::List(vec![Value::Int(1)]);
+    assert_eq!(
+        concat(&[a, Value::Str("x".to_string())]),
+        Err(ErrorKind::UnexpectedType {
+            expected: value::Type::List,
+            actual: value::Type::Str,
+        })
+    );
+}
+
+#[test]
+fn slice_blames_a_bad_bound_argument_not_the_list() {
+    let list = Value::List(vec![Value::Int(1), Value::Int(2)]);
+    assert_eq!(
+        slice(&[list, Value::Int(0), Value::Str("x".to_string())]),
+        Err(ErrorKind::UnexpectedType {
+            expected: value::Type::Int,
+            actual: value::Type::Str,
+        })
+    );
+}
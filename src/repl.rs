@@ -0,0 +1,89 @@
+use crate::{grammar, state::State};
+use rustyline::error::ReadlineError;
+use rustyline::Editor;
+use std::path::PathBuf;
+
+const HISTORY_FILE: &str = ".schwift_history";
+const PROMPT: &str = ">>> ";
+const CONTINUATION_PROMPT: &str = "... ";
+
+#[cfg(test)]
+mod test;
+
+/// Reads statements from stdin and feeds them to a persistent `State`, so
+/// symbols and functions defined in one entry are visible in the next.
+pub fn run(trace: bool) {
+    let mut state = State::new();
+    if trace {
+        state.enable_tracing();
+    }
+    let mut editor = Editor::<()>::new();
+    let history_path = history_path();
+
+    let _ = editor.load_history(&history_path);
+
+    let mut buffer = String::new();
+
+    loop {
+        let prompt = if buffer.is_empty() {
+            PROMPT
+        } else {
+            CONTINUATION_PROMPT
+        };
+
+        match editor.readline(prompt) {
+            Ok(line) => {
+                if !buffer.is_empty() {
+                    buffer.push('\n');
+                }
+                buffer.push_str(&line);
+
+                match grammar::program(&buffer) {
+                    Ok(statements) => {
+                        editor.add_history_entry(buffer.as_str());
+                        buffer.clear();
+
+                        if let Err(e) = state.run(&statements) {
+                            println!("{}", e.panic_message());
+                        }
+                    }
+                    Err(err) => {
+                        if depth(&buffer) <= 0 {
+                            println!("{:?}", err);
+                            editor.add_history_entry(buffer.as_str());
+                            buffer.clear();
+                        }
+                    }
+                }
+            }
+            Err(ReadlineError::Interrupted) | Err(ReadlineError::Eof) => break,
+            Err(_) => break,
+        }
+    }
+
+    let _ = editor.save_history(&history_path);
+}
+
+/// Running nesting depth of `{`/`[`/`(` across the accumulated buffer,
+/// ignoring brackets inside string literals.
+fn depth(buffer: &str) -> i32 {
+    let mut depth = 0;
+    let mut in_string = false;
+
+    for c in buffer.chars() {
+        match c {
+            '"' => in_string = !in_string,
+            '{' | '[' | '(' if !in_string => depth += 1,
+            '}' | ']' | ')' if !in_string => depth -= 1,
+            _ => {}
+        }
+    }
+
+    depth
+}
+
+fn history_path() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(HISTORY_FILE)
+}
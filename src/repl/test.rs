@@ -0,0 +1,35 @@
+use super::*;
+
+#[test]
+fn depth_is_zero_for_balanced_input() {
+    assert_eq!(depth("x = 1"), 0);
+    assert_eq!(depth("if true { x = 1 }"), 0);
+}
+
+#[test]
+fn depth_counts_all_three_bracket_kinds() {
+    assert_eq!(depth("{"), 1);
+    assert_eq!(depth("["), 1);
+    assert_eq!(depth("("), 1);
+    assert_eq!(depth("{[("), 3);
+}
+
+#[test]
+fn depth_goes_negative_on_an_unmatched_close() {
+    assert_eq!(depth(")"), -1);
+}
+
+#[test]
+fn depth_ignores_brackets_inside_string_literals() {
+    assert_eq!(depth(r#"print "{[(""#), 0);
+    assert_eq!(depth(r#"if true { print "}" }"#), 0);
+}
+
+#[test]
+fn depth_treats_an_unterminated_string_as_still_open() {
+    // A `"` flips `in_string`, so brackets after an odd number of quotes
+    // are still being skipped - this is why depth <= 0 alone isn't enough
+    // to call a parse done; an unterminated string is handled by the
+    // parser failing in a way `depth` can't see.
+    assert_eq!(depth(r#"print "{"#), 0);
+}
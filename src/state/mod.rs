@@ -3,20 +3,28 @@ use crate::{
     expression::Expression,
     grammar,
     statement::{Statement, StatementKind},
-    value::{self, Value},
+    trace::{Sink, StdoutSink, TraceEvent},
+    value::{self, Env, EnvRef, Value},
     vec_map::VecMap,
 };
-use std::{borrow, io, mem};
+use std::{borrow, cell::RefCell, io, mem, rc::Rc};
 
 type Map<K, V> = VecMap<K, V>;
 
 #[cfg(test)]
 mod test;
 
+const DEFAULT_MAX_CALL_DEPTH: usize = 512;
+
 pub struct State {
-    symbols: Map<String, Value>,
+    globals: Map<String, Value>,
+    locals: Vec<Map<String, Value>>,
     last_return: Option<Value>,
     libraries: Vec<libloading::Library>,
+    call_depth: usize,
+    max_call_depth: usize,
+    source_file: Option<String>,
+    tracer: Option<Box<dyn Sink>>,
 }
 
 macro_rules! error {
@@ -50,10 +58,10 @@ impl State {
         exp: &Expression,
     ) -> SwResult<borrow::Cow<'a, Value>> {
         let inner_expression_value = exp.evaluate(self)?.into_owned();
-        match self.symbols.get(list_name) {
-            Some(symbol) => match *symbol {
-                Value::List(ref l) => {
-                    if let Value::Int(i) = inner_expression_value {
+        match self.get(list_name) {
+            Ok(symbol) => match *symbol {
+                Value::List(ref l) => match inner_expression_value {
+                    Value::Int(i) => {
                         let index = i as usize;
                         if index < l.len() {
                             Ok(borrow::Cow::Borrowed(&l[index]))
@@ -63,13 +71,12 @@ impl State {
                                 index,
                             })
                         }
-                    } else {
-                        Err(ErrorKind::UnexpectedType {
-                            expected: value::Type::Int,
-                            actual: inner_expression_value.get_type(),
-                        })
                     }
-                }
+                    _ => Err(ErrorKind::UnexpectedType {
+                        expected: value::Type::Int,
+                        actual: inner_expression_value.get_type(),
+                    }),
+                },
                 Value::Str(ref s) => {
                     if let Value::Int(i) = inner_expression_value {
                         let index = i as usize;
@@ -92,23 +99,29 @@ impl State {
                 }
                 _ => Err(ErrorKind::IndexUnindexable(symbol.get_type())),
             },
-            None => Err(ErrorKind::UnknownVariable(list_name.to_string())),
+            Err(e) => Err(e),
         }
     }
 
-    pub fn call_function(&self, name: &str, args: &[Expression]) -> SwResult<Value> {
+    /// Runs the callee against its own captured environment, not the
+    /// caller's locals.
+    pub fn call_function(&mut self, name: &str, args: &[Expression]) -> SwResult<Value> {
         let mut call_args = Vec::new();
 
         for x in args {
             call_args.push(x.evaluate(self)?.into_owned());
         }
 
-        if let Value::NativeFunction(ref funk) = *self.get(name)? {
-            return funk.call(&call_args);
+        if let Value::NativeFunction(funk) = *self.get(name)? {
+            return funk(&call_args);
+        }
+
+        if let Value::Func(func) = *self.get(name)? {
+            return Ok(func.call(&call_args));
         }
 
-        match self.get(name)? {
-            Value::Function(ref params, ref body) => {
+        match self.get(name)?.clone() {
+            Value::Function(ref params, ref body, ref env) => {
                 if args.len() != params.len() {
                     return Err(ErrorKind::InvalidArguments(
                         name.to_string(),
@@ -117,20 +130,31 @@ impl State {
                     ));
                 }
 
-                let mut child_state = Self::default();
+                self.check_call_depth()?;
+                self.call_depth += 1;
+
+                let env = env.env();
+                let mut call_locals = env.borrow().clone();
+                call_locals.push(Map::new());
 
-                for (name, arg) in params.iter().zip(call_args) {
-                    child_state.symbols.insert(name.to_string(), arg);
+                for (param, arg) in params.iter().zip(call_args) {
+                    call_locals.last_mut().unwrap().insert(param.to_string(), arg);
                 }
 
-                match child_state.run(body) {
+                let saved_locals = mem::replace(&mut self.locals, call_locals);
+                let saved_return = mem::replace(&mut self.last_return, None);
+                let run_result = self.run(body);
+                let call_return = mem::replace(&mut self.last_return, saved_return);
+                self.locals = saved_locals;
+
+                self.call_depth -= 1;
+
+                match run_result {
                     Ok(()) => {}
-                    Err(e) => return Err(e.kind),
+                    Err(e) => return Err(e.into_kind()),
                 }
 
-                let last_ret = mem::replace(&mut child_state.last_return, None);
-
-                match last_ret {
+                match call_return {
                     Some(val) => Ok(val),
                     None => Err(ErrorKind::NoReturn(name.to_string())),
                 }
@@ -142,23 +166,71 @@ impl State {
         }
     }
 
+    fn check_call_depth(&self) -> SwResult<()> {
+        if self.call_depth >= self.max_call_depth {
+            Err(ErrorKind::StackOverflow(self.max_call_depth))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Walks `locals` innermost-first, falling back to `globals`.
     pub fn get(&self, name: &str) -> SwResult<&Value> {
-        match self.symbols.get(name) {
-            Some(val) => Ok(val),
-            None => Err(ErrorKind::UnknownVariable(name.to_string())),
+        for scope in self.locals.iter().rev() {
+            if let Some(val) = scope.get(name) {
+                return Ok(val);
+            }
         }
+        self.globals
+            .get(name)
+            .ok_or_else(|| ErrorKind::UnknownVariable(name.to_string()))
     }
 
-    pub fn assign(&mut self, str: String, exp: &Expression) -> SwResult<()> {
+    /// Binds into the innermost scope, so `x = 5` shadows an outer `x`.
+    pub fn assign(&mut self, name: String, exp: &Expression) -> SwResult<()> {
         let v = exp.evaluate(self)?.into_owned();
-        self.symbols.insert(str, v);
+        self.innermost_mut().insert(name, v);
         Ok(())
     }
 
+    /// Binds `name` to a `Value::Function` capturing the current scope
+    /// chain. A second copy of the function, referencing that same captured
+    /// chain by `EnvRef::SelfRef` rather than `EnvRef::Owned`, is patched
+    /// into the chain's own innermost frame too, so a nested closure can
+    /// find itself by name and recurse (see `value::EnvRef`'s doc comment
+    /// for why that copy doesn't hold an owning reference). Top-level
+    /// functions don't need the patch - their binding already lives in
+    /// `globals`, which a call's swapped `locals` never hides - so `locals`
+    /// being empty there is a no-op.
+    fn define_function(&mut self, name: String, params: Vec<String>, body: Vec<Statement>) {
+        let env: Env = Rc::new(RefCell::new(self.locals.clone()));
+        let func = Value::Function(params.clone(), body.clone(), EnvRef::Owned(Rc::clone(&env)));
+
+        if let Some(frame) = env.borrow_mut().last_mut() {
+            let self_ref = Value::Function(params, body, EnvRef::SelfRef(Rc::downgrade(&env)));
+            frame.insert(name.clone(), self_ref);
+        }
+
+        self.innermost_mut().insert(name, func);
+    }
+
     fn delete(&mut self, name: &str) -> SwResult<()> {
-        match self.symbols.remove(name) {
-            Some(_) => Ok(()),
-            None => Err(ErrorKind::UnknownVariable(name.to_string())),
+        for scope in self.locals.iter_mut().rev() {
+            if scope.remove(name).is_some() {
+                return Ok(());
+            }
+        }
+        if self.globals.remove(name).is_some() {
+            return Ok(());
+        }
+        Err(ErrorKind::UnknownVariable(name.to_string()))
+    }
+
+    /// The top local frame if one is live, otherwise `globals`.
+    fn innermost_mut(&mut self) -> &mut Map<String, Value> {
+        match self.locals.last_mut() {
+            Some(scope) => scope,
+            None => &mut self.globals,
         }
     }
 
@@ -183,7 +255,7 @@ impl State {
         }
 
         input = input.trim().to_string();
-        self.symbols.insert(name, Value::Str(input));
+        self.innermost_mut().insert(name, Value::Str(input));
 
         Ok(())
     }
@@ -197,10 +269,14 @@ impl State {
     }
 
     fn get_mut(&mut self, name: &str) -> SwResult<&mut Value> {
-        match self.symbols.get_mut(name) {
-            Some(value) => Ok(value),
-            None => Err(ErrorKind::UnknownVariable(name.to_string())),
+        for scope in self.locals.iter_mut().rev() {
+            if let Some(value) = scope.get_mut(name) {
+                return Ok(value);
+            }
         }
+        self.globals
+            .get_mut(name)
+            .ok_or_else(|| ErrorKind::UnknownVariable(name.to_string()))
     }
 
     fn get_list(&mut self, name: &str) -> SwResult<&mut Vec<Value>> {
@@ -318,14 +394,38 @@ impl State {
         Ok(())
     }
 
-    fn catch(&mut self, try_block: &[Statement], catch: &[Statement]) -> SwErResult<()> {
+    fn catch(
+        &mut self,
+        try_block: &[Statement],
+        catch: &[Statement],
+        binding: &Option<String>,
+    ) -> SwErResult<()> {
         match self.run(try_block) {
             Ok(()) => Ok(()),
-            Err(_) => self.run(catch),
+            Err(err) => {
+                if let Some(name) = binding {
+                    let kind = err.into_kind();
+                    let caught = value::CaughtError {
+                        type_str: kind.type_str(),
+                        message: kind.message(),
+                    };
+                    self.innermost_mut().insert(name.clone(), Value::Error(caught));
+                }
+                self.run(catch)
+            }
         }
     }
 
     pub fn execute(&mut self, statement: &Statement) -> SwErResult<()> {
+        // Assignment, Return, and FunctionCall trace their own richer event
+        // further down, so they're skipped here.
+        match statement.kind {
+            StatementKind::Assignment(_, _)
+            | StatementKind::Return(_)
+            | StatementKind::FunctionCall(_, _) => {}
+            _ => self.trace(statement, statement_kind_name(&statement.kind), None),
+        }
+
         match statement.kind {
             StatementKind::Input(ref s) => try_nop_error!(self.input(s.to_string()), statement),
             StatementKind::ListAssign(ref s, ref index_exp, ref assign_exp) => {
@@ -338,7 +438,7 @@ impl State {
                 try_nop_error!(self.list_delete(name, idx), statement)
             }
             StatementKind::ListNew(ref s) => {
-                self.symbols.insert(s.clone(), Value::List(Vec::new()));
+                self.innermost_mut().insert(s.clone(), Value::List(Vec::new()));
                 Ok(())
             }
             StatementKind::If(ref bool, ref if_body, ref else_body) => {
@@ -346,27 +446,43 @@ impl State {
             }
             StatementKind::While(ref bool, ref body) => self.exec_while(statement, bool, body),
             StatementKind::Assignment(ref name, ref value) => {
-                try_nop_error!(self.assign(name.clone(), value), statement)
+                try_nop_error!(self.assign(name.clone(), value), statement);
+
+                if self.tracer.is_some() {
+                    if let Ok(assigned) = self.get(name) {
+                        let traced = assigned.clone();
+                        self.trace(statement, "Assignment", Some((&traced, traced.type_str())));
+                    }
+                }
+
+                Ok(())
             }
             StatementKind::Delete(ref name) => try_nop_error!(self.delete(name), statement),
             StatementKind::Print(ref exp) => try_nop_error!(self.print(exp), statement),
             StatementKind::PrintNoNl(ref exp) => try_nop_error!(self.print_no_nl(exp), statement),
-            StatementKind::Catch(ref try_block, ref catch) => self.catch(try_block, catch),
+            StatementKind::Catch(ref try_block, ref catch, ref binding) => {
+                self.catch(try_block, catch, binding)
+            }
             StatementKind::Function(ref name, ref args, ref body) => {
-                self.symbols
-                    .insert(name.clone(), Value::Function(args.clone(), body.clone()));
+                self.define_function(name.clone(), args.clone(), body.clone());
                 Ok(())
             }
             StatementKind::Return(ref expr) => {
-                let val = try_error!(expr.evaluate(self), statement);
-                self.last_return = Some(val.into_owned());
+                let val = try_error!(expr.evaluate(self), statement).into_owned();
+                let type_str = val.type_str();
+                self.trace(statement, "Return", Some((&val, type_str)));
+                self.last_return = Some(val);
 
                 Ok(())
             }
 
             StatementKind::FunctionCall(ref name, ref args) => {
                 match self.call_function(name, args) {
-                    Ok(_) => Ok(()),
+                    Ok(val) => {
+                        let type_str = val.type_str();
+                        self.trace(statement, "FunctionCall", Some((&val, type_str)));
+                        Ok(())
+                    }
                     Err(e) => Err(Error::new(e, statement.clone())),
                 }
             }
@@ -393,6 +509,37 @@ impl State {
         Ok(())
     }
 
+    /// Like `run`, but keeps going after an error instead of bailing out,
+    /// collecting every `Error` produced along the way.
+    pub fn run_collecting_errors(&mut self, statements: &[Statement]) -> Result<(), Vec<Error>> {
+        let mut errors = Vec::new();
+
+        for statement in statements {
+            let succeeded = match self.execute(statement) {
+                Ok(()) => true,
+                Err(e) => {
+                    errors.push(e);
+                    false
+                }
+            };
+
+            if succeeded {
+                if let StatementKind::Return(_) = statement.kind {
+                    break;
+                }
+                if self.last_return.is_some() {
+                    break;
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
     fn dylib_load(&mut self, lib_path: &str, functions: &[Statement]) -> SwResult<()> {
         let dylib = libloading::Library::new(lib_path)?;
         for statement in functions {
@@ -421,28 +568,96 @@ impl State {
             value_args.push(grammar::value(arg).unwrap_or_else(|_| Value::Str((*arg).into())));
         }
 
-        self.symbols.insert("argv".into(), value_args.into());
+        self.globals.insert("argv".into(), value_args.into());
     }
 
+    /// Always binds into `globals`, regardless of the current call depth.
     pub fn insert<S, V>(&mut self, name: S, value: V)
     where
         S: Into<String>,
         V: Into<Value>,
     {
-        self.symbols.insert(name.into(), value.into());
+        self.globals.insert(name.into(), value.into());
     }
 
     pub fn new() -> Self {
-        Self::default()
+        let mut state = Self::default();
+        crate::native::register(&mut state);
+        state
+    }
+
+    pub fn set_max_call_depth(&mut self, max_call_depth: usize) {
+        self.max_call_depth = max_call_depth;
+    }
+
+    pub fn set_source_file<S: Into<String>>(&mut self, filename: S) {
+        self.source_file = Some(filename.into());
+    }
+
+    /// Turns on step tracing with the default stdout sink.
+    pub fn enable_tracing(&mut self) {
+        self.tracer = Some(Box::new(StdoutSink));
+    }
+
+    pub fn set_tracer(&mut self, sink: Box<dyn Sink>) {
+        self.tracer = Some(sink);
+    }
+
+    fn trace(&mut self, statement: &Statement, kind: &'static str, value: Option<(&Value, &'static str)>) {
+        if self.tracer.is_none() {
+            return;
+        }
+
+        let source_line = self
+            .source_file
+            .as_ref()
+            .and_then(|filename| statement.get_source(filename).ok());
+
+        let event = TraceEvent {
+            statement,
+            kind,
+            value,
+        };
+
+        self.tracer
+            .as_mut()
+            .unwrap()
+            .record(&event, source_line.as_deref());
+    }
+}
+
+fn statement_kind_name(kind: &StatementKind) -> &'static str {
+    match *kind {
+        StatementKind::Input(_) => "Input",
+        StatementKind::ListAssign(_, _, _) => "ListAssign",
+        StatementKind::ListAppend(_, _) => "ListAppend",
+        StatementKind::ListDelete(_, _) => "ListDelete",
+        StatementKind::ListNew(_) => "ListNew",
+        StatementKind::If(_, _, _) => "If",
+        StatementKind::While(_, _) => "While",
+        StatementKind::Assignment(_, _) => "Assignment",
+        StatementKind::Delete(_) => "Delete",
+        StatementKind::Print(_) => "Print",
+        StatementKind::PrintNoNl(_) => "PrintNoNl",
+        StatementKind::Catch(_, _, _) => "Catch",
+        StatementKind::Function(_, _, _) => "Function",
+        StatementKind::Return(_) => "Return",
+        StatementKind::FunctionCall(_, _) => "FunctionCall",
+        StatementKind::DylibLoad(_, _) => "DylibLoad",
     }
 }
 
 impl Default for State {
     fn default() -> Self {
         Self {
-            symbols: Map::new(),
+            globals: Map::new(),
+            locals: Vec::new(),
             last_return: None,
             libraries: Vec::new(),
+            call_depth: 0,
+            max_call_depth: DEFAULT_MAX_CALL_DEPTH,
+            source_file: None,
+            tracer: None,
         }
     }
 }
@@ -0,0 +1,163 @@
+use super::*;
+
+#[test]
+fn get_lets_an_inner_scope_shadow_an_outer_one() {
+    let mut state = State::default();
+    state.insert("x", Value::Int(1));
+
+    state.locals.push(Map::new());
+    state.locals.last_mut().unwrap().insert("x".to_string(), Value::Int(2));
+
+    assert_eq!(*state.get("x").unwrap(), Value::Int(2));
+
+    state.locals.pop();
+    assert_eq!(*state.get("x").unwrap(), Value::Int(1));
+}
+
+#[test]
+fn unknown_variable_is_still_reported_once_every_scope_is_checked() {
+    let state = State::default();
+    assert_eq!(
+        state.get("nope"),
+        Err(ErrorKind::UnknownVariable("nope".to_string()))
+    );
+}
+
+#[test]
+fn delete_only_removes_from_the_innermost_matching_scope() {
+    let mut state = State::default();
+    state.insert("x", Value::Int(1));
+
+    state.locals.push(Map::new());
+    state.locals.last_mut().unwrap().insert("x".to_string(), Value::Int(2));
+    state.delete("x").unwrap();
+
+    assert_eq!(*state.get("x").unwrap(), Value::Int(1));
+}
+
+#[test]
+fn swapping_locals_for_a_captured_env_hides_the_caller_s_frame() {
+    // Mirrors what call_function does: push a caller-side local ("leak"),
+    // then swap locals out for a callee's captured environment (empty, as
+    // if the callee were defined at the top level) before running its body.
+    let mut state = State::default();
+    state.locals.push(Map::new());
+    state.locals.last_mut().unwrap().insert("leak".to_string(), Value::Int(99));
+
+    let callee_env = Vec::new();
+    let saved_locals = std::mem::replace(&mut state.locals, callee_env);
+
+    assert_eq!(
+        state.get("leak"),
+        Err(ErrorKind::UnknownVariable("leak".to_string()))
+    );
+
+    state.locals = saved_locals;
+    assert_eq!(*state.get("leak").unwrap(), Value::Int(99));
+}
+
+#[test]
+fn swapping_locals_for_a_captured_env_still_sees_globals() {
+    let mut state = State::default();
+    state.insert("shared", Value::Int(7));
+    state.locals.push(Map::new());
+
+    let saved_locals = std::mem::replace(&mut state.locals, Vec::new());
+
+    assert_eq!(*state.get("shared").unwrap(), Value::Int(7));
+
+    state.locals = saved_locals;
+}
+
+#[test]
+fn define_function_at_top_level_does_not_need_a_self_reference_patch() {
+    let mut state = State::default();
+    state.define_function("f".to_string(), Vec::new(), Vec::new());
+
+    match state.get("f").unwrap() {
+        Value::Function(_, _, env) => assert!(env.env().borrow().is_empty()),
+        other => panic!("expected a Value::Function, got {:?}", other),
+    }
+}
+
+#[test]
+fn define_function_in_a_nested_scope_can_see_itself_for_recursion() {
+    let mut state = State::default();
+    state.locals.push(Map::new());
+
+    state.define_function("fact".to_string(), vec!["n".to_string()], Vec::new());
+
+    match state.get("fact").unwrap() {
+        Value::Function(_, _, env) => {
+            let frames = env.env();
+            let frames = frames.borrow();
+            let self_binding = frames.last().and_then(|frame| frame.get("fact"));
+            assert!(matches!(self_binding, Some(Value::Function(_, _, _))));
+        }
+        other => panic!("expected a Value::Function, got {:?}", other),
+    }
+}
+
+#[test]
+fn defining_a_nested_function_does_not_leak_a_reference_cycle() {
+    // Mirrors call_function's lifecycle: a local frame holds the nested
+    // function's definition for the duration of one call, then is dropped
+    // when the call returns. Before the self-reference patch used a Weak,
+    // this never dropped - the environment's own frame held a strong Rc
+    // back to itself.
+    let mut state = State::default();
+    state.locals.push(Map::new());
+    state.define_function("helper".to_string(), Vec::new(), Vec::new());
+
+    let weak_env = match state.get("helper").unwrap() {
+        Value::Function(_, _, env) => std::rc::Rc::downgrade(&env.env()),
+        other => panic!("expected a Value::Function, got {:?}", other),
+    };
+
+    state.locals.pop();
+
+    assert!(weak_env.upgrade().is_none());
+}
+
+#[test]
+fn run_collecting_errors_succeeds_on_an_empty_program() {
+    // A real statement that fails mid-run needs a Statement that actually
+    // errors, which needs the parser/expression evaluator to build - out
+    // of reach from this module. This covers the base case the rest
+    // builds on: no statements, no errors, Ok(()) rather than Err(vec![]).
+    let mut state = State::default();
+    assert!(matches!(state.run_collecting_errors(&[]), Ok(())));
+}
+
+#[test]
+fn catch_does_not_bind_anything_when_the_try_block_succeeds() {
+    // A real failing try_block needs a Statement that actually errors,
+    // which needs the parser/expression evaluator to build - out of reach
+    // from this module. This covers the part that doesn't: the binding is
+    // only ever inserted on the error path, never on success.
+    let mut state = State::default();
+    let result = state.catch(&[], &[], &Some("e".to_string()));
+
+    assert!(matches!(result, Ok(())));
+    assert_eq!(
+        state.get("e"),
+        Err(ErrorKind::UnknownVariable("e".to_string()))
+    );
+}
+
+#[test]
+fn check_call_depth_allows_calls_under_the_limit() {
+    let mut state = State::default();
+    state.max_call_depth = 1;
+
+    assert_eq!(state.check_call_depth(), Ok(()));
+}
+
+#[test]
+fn check_call_depth_rejects_calls_at_the_limit() {
+    let mut state = State::default();
+    state.max_call_depth = 1;
+    state.call_depth = 1;
+
+    assert_eq!(state.check_call_depth(), Err(ErrorKind::StackOverflow(1)));
+}
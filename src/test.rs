@@ -0,0 +1,26 @@
+use super::*;
+
+#[test]
+fn take_flag_removes_the_flag_and_reports_it_was_present() {
+    let mut args = vec!["schwift".to_string(), "--trace".to_string(), "a.sw".to_string()];
+    assert!(take_flag(&mut args, "--trace"));
+    assert_eq!(args, vec!["schwift".to_string(), "a.sw".to_string()]);
+}
+
+#[test]
+fn take_flag_reports_false_and_leaves_args_untouched_when_absent() {
+    let mut args = vec!["schwift".to_string(), "a.sw".to_string()];
+    assert!(!take_flag(&mut args, "--trace"));
+    assert_eq!(args, vec!["schwift".to_string(), "a.sw".to_string()]);
+}
+
+#[test]
+fn take_flag_does_not_strip_the_flag_from_the_script_s_own_arguments() {
+    // schwift a.sw --trace: that --trace belongs to the script, not us.
+    let mut args = vec!["schwift".to_string(), "a.sw".to_string(), "--trace".to_string()];
+    assert!(!take_flag(&mut args, "--trace"));
+    assert_eq!(
+        args,
+        vec!["schwift".to_string(), "a.sw".to_string(), "--trace".to_string()]
+    );
+}
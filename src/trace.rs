@@ -0,0 +1,31 @@
+use crate::{statement::Statement, value::Value};
+
+/// One step of execution, handed to a `Sink` by `State::execute` when
+/// tracing is enabled.
+pub struct TraceEvent<'a> {
+    pub statement: &'a Statement,
+    pub kind: &'static str,
+    pub value: Option<(&'a Value, &'static str)>,
+}
+
+/// Where traced events go. Kept as a trait so embedders can capture events
+/// into a buffer instead of printing them.
+pub trait Sink {
+    fn record(&mut self, event: &TraceEvent, source_line: Option<&str>);
+}
+
+/// The default sink: prints one line per traced statement.
+pub struct StdoutSink;
+
+impl Sink for StdoutSink {
+    fn record(&mut self, event: &TraceEvent, source_line: Option<&str>) {
+        let line = source_line.unwrap_or("<unknown>");
+
+        match event.value {
+            Some((value, type_str)) => {
+                println!("[trace] {} | {} => {:?} ({})", line, event.kind, value, type_str)
+            }
+            None => println!("[trace] {} | {}", line, event.kind),
+        }
+    }
+}
@@ -0,0 +1,236 @@
+use crate::{error::SwResult, statement::Statement, vec_map::VecMap};
+use std::{
+    cell::RefCell,
+    fmt,
+    rc::{Rc, Weak},
+};
+
+#[cfg(test)]
+mod test;
+
+/// A closure's captured scope chain.
+pub type Env = Rc<RefCell<Vec<VecMap<String, Value>>>>;
+
+type Frames = RefCell<Vec<VecMap<String, Value>>>;
+
+/// How a `Value::Function` refers to its captured `Env`.
+///
+/// `State::define_function` patches a function's own binding into its own
+/// captured environment, so a recursive call inside the body can find
+/// itself by name (see that method's doc comment). If that patched-in copy
+/// held an owning `Owned(Env)` the same way the function's outward binding
+/// does, the environment would hold an `Rc` back to itself: a reference
+/// cycle that never drops, leaking one frame chain per *call* that runs the
+/// `Function` statement (unbounded in a loop or a long-running REPL
+/// session). The self-reference holds `SelfRef` (a `Weak`) instead, which
+/// can only be upgraded for as long as some other owner - the function's
+/// outward binding, or an in-progress call - is keeping the environment
+/// alive anyway.
+#[derive(Clone)]
+pub enum EnvRef {
+    Owned(Env),
+    SelfRef(Weak<Frames>),
+}
+
+impl EnvRef {
+    /// The live `Env`, upgrading a `SelfRef` first. Panics if a `SelfRef`'s
+    /// environment has already been dropped, which would mean this
+    /// `Value::Function` outlived every strong owner of its own scope -
+    /// i.e. it's being called after nothing should still be able to reach
+    /// it.
+    pub fn env(&self) -> Env {
+        match *self {
+            EnvRef::Owned(ref env) => Rc::clone(env),
+            EnvRef::SelfRef(ref weak) => weak
+                .upgrade()
+                .expect("function called after its captured environment was dropped"),
+        }
+    }
+
+    fn ptr(&self) -> *const Frames {
+        match *self {
+            EnvRef::Owned(ref env) => Rc::as_ptr(env),
+            EnvRef::SelfRef(ref weak) => weak.as_ptr(),
+        }
+    }
+}
+
+/// A dylib-exported Schwift function.
+pub type _Func = unsafe extern "C" fn(&[Value]) -> Value;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Func(_Func);
+
+impl Func {
+    pub fn new(f: _Func) -> Self {
+        Func(f)
+    }
+
+    pub fn call(&self, args: &[Value]) -> Value {
+        unsafe { (self.0)(args) }
+    }
+}
+
+/// A snapshot of an `ErrorKind` bound into a script by a `catch` block.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CaughtError {
+    pub type_str: &'static str,
+    pub message: String,
+}
+
+#[derive(Clone)]
+pub enum Value {
+    Int(i64),
+    Bool(bool),
+    Str(String),
+    List(Vec<Value>),
+    /// Params, body, and the lexical scope chain captured at definition time.
+    Function(Vec<String>, Vec<Statement>, EnvRef),
+    NativeFunction(fn(&[Value]) -> SwResult<Value>),
+    Func(Func),
+    Error(CaughtError),
+}
+
+/// Hand-rolled rather than derived: a function's captured environment can
+/// hold a self-reference (see `EnvRef`'s doc comment), and a derived impl
+/// would need to walk into that to compare it, recursing forever. Two
+/// functions compare equal by params/body and captured-environment
+/// identity, not by deep-comparing the environment's contents.
+impl PartialEq for Value {
+    fn eq(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Value::Int(a), Value::Int(b)) => a == b,
+            (Value::Bool(a), Value::Bool(b)) => a == b,
+            (Value::Str(a), Value::Str(b)) => a == b,
+            (Value::List(a), Value::List(b)) => a == b,
+            (Value::Function(ap, ab, ae), Value::Function(bp, bb, be)) => {
+                ap == bp && ab == bb && ae.ptr() == be.ptr()
+            }
+            (Value::NativeFunction(a), Value::NativeFunction(b)) => a == b,
+            (Value::Func(a), Value::Func(b)) => a == b,
+            (Value::Error(a), Value::Error(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+/// Hand-rolled for the same reason as `PartialEq`: printing `Function`'s
+/// captured environment could walk into a self-reference.
+impl fmt::Debug for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Value::Int(ref i) => f.debug_tuple("Int").field(i).finish(),
+            Value::Bool(ref b) => f.debug_tuple("Bool").field(b).finish(),
+            Value::Str(ref s) => f.debug_tuple("Str").field(s).finish(),
+            Value::List(ref l) => f.debug_tuple("List").field(l).finish(),
+            Value::Function(ref params, ref body, _) => f
+                .debug_tuple("Function")
+                .field(params)
+                .field(body)
+                .field(&"<env>")
+                .finish(),
+            Value::NativeFunction(_) => f.debug_tuple("NativeFunction").finish(),
+            Value::Func(ref func) => f.debug_tuple("Func").field(func).finish(),
+            Value::Error(ref e) => f.debug_tuple("Error").field(e).finish(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Type {
+    Int,
+    Bool,
+    Str,
+    List,
+    Function,
+    Error,
+}
+
+impl Type {
+    pub fn type_str(self) -> &'static str {
+        match self {
+            Type::Int => "Int",
+            Type::Bool => "Bool",
+            Type::Str => "Str",
+            Type::List => "List",
+            Type::Function => "Function",
+            Type::Error => "Error",
+        }
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.type_str())
+    }
+}
+
+impl Value {
+    pub fn get_type(&self) -> Type {
+        match *self {
+            Value::Int(_) => Type::Int,
+            Value::Bool(_) => Type::Bool,
+            Value::Str(_) => Type::Str,
+            Value::List(_) => Type::List,
+            Value::Function(_, _, _) | Value::NativeFunction(_) | Value::Func(_) => Type::Function,
+            Value::Error(_) => Type::Error,
+        }
+    }
+
+    pub fn type_str(&self) -> &'static str {
+        self.get_type().type_str()
+    }
+
+    pub fn print(&self) {
+        print!("{}", self);
+    }
+
+    pub fn println(&self) {
+        println!("{}", self);
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Value::Int(i) => write!(f, "{}", i),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Str(ref s) => write!(f, "{}", s),
+            Value::List(ref l) => write!(f, "{:?}", l),
+            Value::Function(_, _, _) => write!(f, "<function>"),
+            Value::NativeFunction(_) => write!(f, "<native function>"),
+            Value::Func(_) => write!(f, "<dylib function>"),
+            Value::Error(ref e) => write!(f, "{}: {}", e.type_str, e.message),
+        }
+    }
+}
+
+impl From<i64> for Value {
+    fn from(i: i64) -> Self {
+        Value::Int(i)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Self {
+        Value::Bool(b)
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Value::Str(s)
+    }
+}
+
+impl From<Vec<Value>> for Value {
+    fn from(l: Vec<Value>) -> Self {
+        Value::List(l)
+    }
+}
+
+impl From<Func> for Value {
+    fn from(f: Func) -> Self {
+        Value::Func(f)
+    }
+}
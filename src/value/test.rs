@@ -0,0 +1,36 @@
+use super::*;
+
+fn caught(type_str: &'static str, message: &str) -> CaughtError {
+    CaughtError {
+        type_str,
+        message: message.to_string(),
+    }
+}
+
+#[test]
+fn value_error_displays_as_type_colon_message() {
+    let err = Value::Error(caught("UnknownVariable", "There's no x in this universe, Morty!"));
+    assert_eq!(
+        err.to_string(),
+        "UnknownVariable: There's no x in this universe, Morty!"
+    );
+}
+
+#[test]
+fn value_error_reports_its_type_as_error() {
+    let err = Value::Error(caught("UnknownVariable", "nope"));
+    assert_eq!(err.get_type(), Type::Error);
+    assert_eq!(err.type_str(), "Error");
+}
+
+#[test]
+fn caught_errors_of_the_same_kind_and_message_are_equal() {
+    assert_eq!(
+        Value::Error(caught("IndexOutOfBounds", "nope")),
+        Value::Error(caught("IndexOutOfBounds", "nope"))
+    );
+    assert_ne!(
+        Value::Error(caught("IndexOutOfBounds", "nope")),
+        Value::Error(caught("UnknownVariable", "nope"))
+    );
+}